@@ -1,11 +1,29 @@
 use clap::Parser;
 
-/// Simple program to greet a person
+use crate::commands::Commands;
+
+/// CLI entrypoint: turn a natural language request into a shell command.
 #[derive(Parser, Debug, Clone)]
 #[command(version, about, long_about = None)]
 pub struct Args {
-    pub(crate) input: String,
+    #[command(subcommand)]
+    pub(crate) command: Option<Commands>,
+
+    /// Natural language query; shorthand for `knock run <input>`
+    pub(crate) input: Option<String>,
 
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     pub(crate) verbose: bool,
+
+    /// Bypass the response cache and always hit the API
+    #[arg(long = "no-cache", global = true)]
+    pub(crate) no_cache: bool,
+
+    /// Run the generated command instead of copying it to the clipboard
+    #[arg(short = 'x', long, global = true)]
+    pub(crate) execute: bool,
+
+    /// Allow running commands that look destructive (after a second confirmation)
+    #[arg(long, global = true)]
+    pub(crate) force: bool,
 }
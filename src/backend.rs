@@ -0,0 +1,172 @@
+use async_openai::{Client, config::OpenAIConfig, types::responses::CreateResponseArgs};
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use crate::config::Config;
+
+/// Generation parameters common to every backend.
+pub struct GenOpts {
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens: u32,
+}
+
+/// A source of command completions. `RequestClient` talks to whichever
+/// backend `Config::provider` selects, so it doesn't need to know whether
+/// it's hitting OpenAI or a local/self-hosted gateway.
+#[async_trait]
+pub trait Backend: Send + Sync {
+    async fn complete(&self, system: &str, prompt: &str, opts: &GenOpts) -> anyhow::Result<String>;
+}
+
+/// The original backend, talking to OpenAI's Responses API.
+pub struct OpenAIBackend {
+    client: Client<OpenAIConfig>,
+}
+
+impl OpenAIBackend {
+    pub fn new() -> Self {
+        Self {
+            client: Client::new(),
+        }
+    }
+}
+
+impl Default for OpenAIBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl Backend for OpenAIBackend {
+    async fn complete(
+        &self,
+        system: &str,
+        prompt: &str,
+        opts: &GenOpts,
+    ) -> anyhow::Result<String> {
+        let request = CreateResponseArgs::default()
+            .model(opts.model.clone())
+            .instructions(system)
+            .input(prompt)
+            .temperature(opts.temperature)
+            .max_output_tokens(opts.max_tokens)
+            .build()?;
+
+        let response = self.client.responses().create(request).await?;
+        response
+            .output_text()
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("empty response from OpenAI"))
+    }
+}
+
+#[derive(Serialize)]
+struct ChatMessage<'a> {
+    role: &'a str,
+    content: &'a str,
+}
+
+#[derive(Serialize)]
+struct ChatRequest<'a> {
+    model: &'a str,
+    messages: Vec<ChatMessage<'a>>,
+    temperature: f32,
+    max_tokens: u32,
+}
+
+#[derive(Deserialize)]
+struct ChatResponse {
+    choices: Vec<ChatChoice>,
+}
+
+#[derive(Deserialize)]
+struct ChatChoice {
+    message: ChatChoiceMessage,
+}
+
+#[derive(Deserialize)]
+struct ChatChoiceMessage {
+    content: String,
+}
+
+/// Generic OpenAI-compatible chat backend (Ollama, LM Studio, or any
+/// gateway that speaks `{model, messages}` in and `choices[].message` out).
+pub struct HttpChatBackend {
+    http: reqwest::Client,
+    base_url: String,
+    api_key: Option<String>,
+}
+
+impl HttpChatBackend {
+    pub fn new(base_url: String, api_key: Option<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            base_url,
+            api_key,
+        }
+    }
+}
+
+#[async_trait]
+impl Backend for HttpChatBackend {
+    async fn complete(
+        &self,
+        system: &str,
+        prompt: &str,
+        opts: &GenOpts,
+    ) -> anyhow::Result<String> {
+        let body = ChatRequest {
+            model: &opts.model,
+            messages: vec![
+                ChatMessage {
+                    role: "system",
+                    content: system,
+                },
+                ChatMessage {
+                    role: "user",
+                    content: prompt,
+                },
+            ],
+            temperature: opts.temperature,
+            max_tokens: opts.max_tokens,
+        };
+
+        let url = format!("{}/chat/completions", self.base_url.trim_end_matches('/'));
+        let mut request = self.http.post(url).json(&body);
+        if let Some(api_key) = &self.api_key {
+            request = request.bearer_auth(api_key);
+        }
+
+        let response: ChatResponse = request.send().await?.error_for_status()?.json().await?;
+        response
+            .choices
+            .into_iter()
+            .next()
+            .map(|choice| choice.message.content)
+            .ok_or_else(|| anyhow::anyhow!("empty response from {}", self.base_url))
+    }
+}
+
+/// Builds the backend selected by `config.provider` ("openai" or "http").
+pub fn from_config(config: &Config) -> Box<dyn Backend> {
+    // Populate the process environment from `~/.knock/.env` (OPENAI_API_KEY,
+    // and whatever `api_key_env` points at) before either backend reads it.
+    crate::config::load_env();
+
+    match config.provider.as_str() {
+        "http" => {
+            let base_url = config
+                .base_url
+                .clone()
+                .unwrap_or_else(|| "http://localhost:11434/v1".to_string());
+            let api_key = config
+                .api_key_env
+                .as_ref()
+                .and_then(|var| std::env::var(var).ok());
+            Box::new(HttpChatBackend::new(base_url, api_key))
+        }
+        _ => Box::new(OpenAIBackend::new()),
+    }
+}
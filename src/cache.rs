@@ -1,4 +1,16 @@
 use std::path::PathBuf;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+/// How long a cached response stays valid before it's treated as a miss.
+pub const DEFAULT_MAX_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+#[derive(Serialize, Deserialize)]
+struct CacheEntry {
+    response: String,
+    created_at_unix: u64,
+}
 
 pub struct Cache {
     db: sled::Db,
@@ -15,27 +27,76 @@ impl Cache {
         Self { db }
     }
 
-    pub fn get(&self, key: &str) -> Option<String> {
-        self.db
-            .get(key)
-            .ok()
-            .flatten()
-            .and_then(|bytes| String::from_utf8(bytes.to_vec()).ok())
+    /// Returns the cached response for `key`, unless it's older than `max_age`,
+    /// in which case the stale entry is evicted and treated as a miss.
+    pub fn get(&self, key: &str, max_age: Duration) -> Option<String> {
+        let bytes = self.db.get(key).ok().flatten()?;
+        let entry: CacheEntry = serde_json::from_slice(&bytes).ok()?;
+
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        if now.saturating_sub(entry.created_at_unix) > max_age.as_secs() {
+            let _ = self.db.remove(key);
+            return None;
+        }
+
+        Some(entry.response)
     }
 
     pub fn insert(&self, key: String, response: String) {
-        let _ = self.db.insert(key, response.as_bytes());
+        let created_at_unix = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+        let entry = CacheEntry {
+            response,
+            created_at_unix,
+        };
+        if let Ok(bytes) = serde_json::to_vec(&entry) {
+            let _ = self.db.insert(key, bytes);
+        }
     }
 
+    /// Removes every cached response.
+    pub fn clear(&self) {
+        let _ = self.db.clear();
+    }
+
+    /// Number of cached responses.
+    pub fn len(&self) -> usize {
+        self.db.len()
+    }
+
+    /// Cache keys, in no particular order.
+    pub fn keys(&self) -> impl Iterator<Item = String> + '_ {
+        self.db
+            .iter()
+            .keys()
+            .filter_map(Result::ok)
+            .map(|key| String::from_utf8_lossy(&key).into_owned())
+    }
+
+    /// Hashes the query fields into a stable cache key.
+    ///
+    /// `DefaultHasher` is explicitly documented as unstable across Rust
+    /// releases, which would make a cache written by one build of `knock`
+    /// unreadable by the next. FNV-1a has no such guarantee to break.
     pub fn generate_key(query: &str, os: &str, shell: &str, mode: &str) -> String {
-        use std::collections::hash_map::DefaultHasher;
-        use std::hash::{Hash, Hasher};
-
-        let mut hasher = DefaultHasher::new();
-        query.hash(&mut hasher);
-        os.hash(&mut hasher);
-        shell.hash(&mut hasher);
-        mode.hash(&mut hasher);
-        format!("{:x}", hasher.finish())
+        const FNV_OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+        const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+
+        let mut hash = FNV_OFFSET_BASIS;
+        for part in [query, os, shell, mode] {
+            for byte in part.as_bytes() {
+                hash ^= u64::from(*byte);
+                hash = hash.wrapping_mul(FNV_PRIME);
+            }
+            // Delimiter between parts so e.g. ("ab", "c") and ("a", "bc") don't collide.
+            hash ^= 0x00;
+            hash = hash.wrapping_mul(FNV_PRIME);
+        }
+        format!("{hash:x}")
     }
 }
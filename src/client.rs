@@ -1,7 +1,7 @@
-use crate::args::Args;
-use async_openai::{
-    Client, config::OpenAIConfig, error::OpenAIError, types::responses::CreateResponseArgs,
-};
+use crate::backend::{self, Backend, GenOpts};
+use crate::cache::Cache;
+use crate::config::Config;
+use crate::environment::Environment;
 
 const INSTRUCTIONS: &str = r#"
 <system_instructions>
@@ -165,41 +165,76 @@ OPTIONS:
 </system_instructions>
 "#;
 
+/// Talks to the configured model to translate a query into a shell command.
+///
+/// Decoupled from the CLI layer (`Args`/`Commands`) so it can be driven by
+/// any subcommand, not just `knock run`.
 pub struct RequestClient {
-    args: Args,
-    client: Client<OpenAIConfig>,
+    input: String,
+    verbose: bool,
+    no_cache: bool,
+    config: Config,
+    backend: Box<dyn Backend>,
 }
 
 impl RequestClient {
-    pub fn new(args: Args) -> Self {
-        let client = Client::new();
-        Self { args, client }
+    pub fn new(input: String, verbose: bool, no_cache: bool, config: Config) -> Self {
+        let backend = backend::from_config(&config);
+        Self {
+            input,
+            verbose,
+            no_cache,
+            config,
+            backend,
+        }
     }
 
-    fn gen_prompt(args: &Args) -> String {
-        let mut prompt_parts = vec![args.input.as_str()];
-        if args.verbose {
-            prompt_parts.push(" [verbose]")
+    fn gen_prompt(&self, env: &Environment) -> String {
+        let mut prompt = format!(
+            "<context os=\"{}\" shell=\"{}\" managers=\"{}\">\n{}",
+            env.os,
+            env.shell,
+            env.managers.join(","),
+            self.input
+        );
+        if self.verbose {
+            prompt.push_str(" [verbose]");
         }
-        prompt_parts.join("")
+        prompt
+    }
+
+    fn mode(&self) -> &'static str {
+        if self.verbose { "verbose" } else { "standard" }
     }
 
-    pub async fn make_request(&self) -> Result<String, OpenAIError> {
-        let prompt = Self::gen_prompt(&self.args);
-        let request = CreateResponseArgs::default()
-            .model("gpt-5.1")
-            .instructions(INSTRUCTIONS)
-            .input(prompt)
-            .temperature(0.2)
-            .max_output_tokens(if self.args.verbose { 512u32 } else { 256u32 })
-            .build()?;
-
-        let response = self.client.responses().create(request).await?;
-
-        if let Some(text) = response.output_text() {
-            Ok(text.clone())
-        } else {
-            Err(OpenAIError::InvalidArgument("Empty response".to_string()))
+    pub async fn make_request(&self, cache: &Cache) -> anyhow::Result<String> {
+        let env = Environment::detect();
+        let key = Cache::generate_key(&self.input, &env.os, &env.shell, self.mode());
+
+        let max_age = std::time::Duration::from_secs(self.config.cache_max_age_secs);
+        if !self.no_cache {
+            if let Some(cached) = cache.get(&key, max_age) {
+                return Ok(cached);
+            }
         }
+
+        let prompt = self.gen_prompt(&env);
+        let opts = GenOpts {
+            model: self.config.model.clone(),
+            temperature: self.config.temperature,
+            max_tokens: if self.verbose {
+                self.config.max_tokens_verbose
+            } else {
+                self.config.max_tokens_standard
+            },
+        };
+
+        let text = self.backend.complete(INSTRUCTIONS, &prompt, &opts).await?;
+
+        if !self.no_cache {
+            cache.insert(key, text.clone());
+        }
+
+        Ok(text)
     }
 }
@@ -0,0 +1,86 @@
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// Ordered fallback chain of clipboard providers for this platform. The
+/// first one found on `PATH` that actually succeeds wins.
+#[cfg(target_os = "macos")]
+const CANDIDATES: &[(&str, &[&str])] = &[("pbcopy", &[])];
+
+#[cfg(target_os = "linux")]
+const CANDIDATES: &[(&str, &[&str])] = &[
+    ("wl-copy", &[]),
+    ("xclip", &["-selection", "clipboard"]),
+    ("xsel", &["--clipboard", "--input"]),
+];
+
+#[cfg(target_os = "windows")]
+const CANDIDATES: &[(&str, &[&str])] = &[
+    ("clip.exe", &[]),
+    // `Set-Clipboard` doesn't read the process's own stdin; it reads from
+    // PowerShell's pipeline, so the piped text has to be routed through `$input`.
+    ("powershell", &["-Command", "$input | Set-Clipboard"]),
+];
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+const CANDIDATES: &[(&str, &[&str])] = &[];
+
+/// Copies `text` to the clipboard.
+///
+/// If `override_command` (from `Config::clipboard_command`) is given, only
+/// that command is tried and its failure is returned as an error. Otherwise
+/// walks the platform's fallback chain in order. Only errors once every
+/// candidate has failed; a missing clipboard utility is reported as a
+/// warning, not a panic, since the printed command is still usable by hand.
+pub fn copy_to_clipboard(
+    text: &str,
+    override_command: Option<&str>,
+) -> Result<(), Box<dyn std::error::Error>> {
+    if let Some(command) = override_command {
+        let (program, args) = split_command(command);
+        return run(program, &args, text);
+    }
+
+    let mut last_error = None;
+    for (program, args) in CANDIDATES {
+        match run(program, args, text) {
+            Ok(()) => return Ok(()),
+            Err(e) if is_not_found(e.as_ref()) => {}
+            Err(e) => last_error = Some(e),
+        }
+    }
+
+    if let Some(e) = last_error {
+        return Err(e);
+    }
+    eprintln!("warning: no clipboard utility found; copy the command above manually");
+    Ok(())
+}
+
+fn is_not_found(error: &(dyn std::error::Error + 'static)) -> bool {
+    error
+        .downcast_ref::<std::io::Error>()
+        .is_some_and(|e| e.kind() == std::io::ErrorKind::NotFound)
+}
+
+fn split_command(command: &str) -> (&str, Vec<&str>) {
+    let mut parts = command.split_whitespace();
+    let program = parts.next().unwrap_or("");
+    (program, parts.collect())
+}
+
+fn run(program: &str, args: &[&str], text: &str) -> Result<(), Box<dyn std::error::Error>> {
+    let mut child = Command::new(program)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    child.stdin.as_mut().unwrap().write_all(text.as_bytes())?;
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(format!("{program} exited with {status}").into())
+    }
+}
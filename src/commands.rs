@@ -0,0 +1,142 @@
+use clap::{Args as ClapArgs, Subcommand};
+use colored::Colorize;
+use enum_dispatch::enum_dispatch;
+
+use crate::cache::Cache;
+use crate::client::RequestClient;
+use crate::clipboard;
+use crate::config::{self, Config};
+use crate::shell;
+
+/// Shared state handed to every subcommand's `execute`.
+pub struct Context {
+    pub cache: Cache,
+    pub config: Config,
+    pub verbose: bool,
+    pub no_cache: bool,
+    pub execute: bool,
+    pub force: bool,
+}
+
+#[enum_dispatch]
+pub trait Execute {
+    async fn execute(&self, ctx: &Context) -> anyhow::Result<()>;
+}
+
+#[derive(Subcommand, Debug, Clone)]
+#[enum_dispatch(Execute)]
+pub enum Commands {
+    /// Translate a natural language query into a shell command (default)
+    Run(RunCommand),
+    /// Scaffold `~/.knock/` and prompt for an API key
+    Init(InitCommand),
+    /// Inspect or update the persisted config
+    Config(ConfigCommand),
+    /// Inspect or clear the response cache
+    Cache(CacheCommand),
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct RunCommand {
+    /// Natural language query to translate
+    pub input: String,
+}
+
+impl Execute for RunCommand {
+    async fn execute(&self, ctx: &Context) -> anyhow::Result<()> {
+        let client = RequestClient::new(
+            self.input.clone(),
+            ctx.verbose,
+            ctx.no_cache,
+            ctx.config.clone(),
+        );
+        let res = client.make_request(&ctx.cache).await?;
+
+        println!("{}", res.bright_cyan());
+        if ctx.execute {
+            shell::confirm_and_run(&res, ctx.force)?;
+        } else if !ctx.verbose {
+            clipboard::copy_to_clipboard(&res, ctx.config.clipboard_command.as_deref())
+                .map_err(|e| anyhow::anyhow!(e))?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct InitCommand;
+
+impl Execute for InitCommand {
+    async fn execute(&self, _ctx: &Context) -> anyhow::Result<()> {
+        let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+        let knock_dir = std::path::PathBuf::from(home).join(".knock");
+        std::fs::create_dir_all(&knock_dir)?;
+
+        print!("Enter your OpenAI API key: ");
+        std::io::Write::flush(&mut std::io::stdout())?;
+        let mut key = String::new();
+        std::io::stdin().read_line(&mut key)?;
+
+        config::save_api_key(key.trim())?;
+        Config::default().save()?;
+        println!("Initialized knock config at {}", knock_dir.display());
+        Ok(())
+    }
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct ConfigCommand {
+    #[command(subcommand)]
+    pub action: ConfigAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum ConfigAction {
+    Get { field: String },
+    Set { field: String, value: String },
+}
+
+impl Execute for ConfigCommand {
+    async fn execute(&self, _ctx: &Context) -> anyhow::Result<()> {
+        match &self.action {
+            ConfigAction::Get { field } => match Config::load().get_field(field) {
+                Some(value) => println!("{value}"),
+                None => println!("{field} is not set"),
+            },
+            ConfigAction::Set { field, value } => {
+                let mut config = Config::load();
+                config.set_field(field, value)?;
+                config.save()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[derive(ClapArgs, Debug, Clone)]
+pub struct CacheCommand {
+    #[command(subcommand)]
+    pub action: CacheAction,
+}
+
+#[derive(Subcommand, Debug, Clone)]
+pub enum CacheAction {
+    Clear,
+    Stats,
+    List,
+}
+
+impl Execute for CacheCommand {
+    async fn execute(&self, ctx: &Context) -> anyhow::Result<()> {
+        match self.action {
+            CacheAction::Clear => ctx.cache.clear(),
+            CacheAction::Stats => println!("{} cached response(s)", ctx.cache.len()),
+            CacheAction::List => {
+                for key in ctx.cache.keys() {
+                    println!("{key}");
+                }
+            }
+        }
+        Ok(())
+    }
+}
@@ -1,7 +1,161 @@
+use std::path::PathBuf;
+
 use dotenvy::dotenv;
+use serde::{Deserialize, Serialize};
+
+fn knock_dir() -> PathBuf {
+    let home = std::env::var("HOME").unwrap_or_else(|_| ".".to_string());
+    PathBuf::from(home).join(".knock")
+}
 
-pub fn init() -> String {
+fn config_path() -> PathBuf {
+    knock_dir().join("config.toml")
+}
+
+fn env_path() -> PathBuf {
+    knock_dir().join(".env")
+}
+
+/// Loads `~/.knock/.env` (falling back to a `.env` in the current directory
+/// for local development) into the process environment. `async_openai`'s
+/// `Client::new()` and any custom `api_key_env` both read their key straight
+/// out of the environment, so this must run before a `Backend` is built.
+pub fn load_env() {
+    dotenvy::from_path(env_path()).ok();
     dotenv().ok();
+}
+
+/// Persists the API key into `~/.knock/.env`, under the name
+/// `async_openai::Client::new()` actually reads it back from.
+pub fn save_api_key(key: &str) -> std::io::Result<()> {
+    let path = env_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, format!("OPENAI_API_KEY={key}\n"))
+}
+
+/// Tunables that used to be hardcoded in `RequestClient`.
+///
+/// Loaded from `~/.knock/config.toml`, layered under `KNOCK_*` environment
+/// variable overrides. There are no CLI flags for these fields yet; use
+/// `knock config set <field> <value>` or the `KNOCK_*` variables instead.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Config {
+    pub model: String,
+    pub temperature: f32,
+    pub max_tokens_standard: u32,
+    pub max_tokens_verbose: u32,
+    pub clipboard_command: Option<String>,
+    /// Which `Backend` to use: "openai" (default) or "http".
+    pub provider: String,
+    /// Base URL for the "http" provider, e.g. a local Ollama/LM Studio gateway.
+    pub base_url: Option<String>,
+    /// Name of the environment variable holding the "http" provider's API key.
+    pub api_key_env: Option<String>,
+    /// How long a cached response stays valid before it's treated as a miss.
+    pub cache_max_age_secs: u64,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            model: "gpt-5.1".to_string(),
+            temperature: 0.2,
+            max_tokens_standard: 256,
+            max_tokens_verbose: 512,
+            clipboard_command: None,
+            provider: "openai".to_string(),
+            base_url: None,
+            api_key_env: None,
+            cache_max_age_secs: crate::cache::DEFAULT_MAX_AGE.as_secs(),
+        }
+    }
+}
+
+impl Config {
+    pub fn load() -> Self {
+        let mut config: Config = std::fs::read_to_string(config_path())
+            .ok()
+            .and_then(|contents| toml::from_str(&contents).ok())
+            .unwrap_or_default();
+
+        config.apply_env_overrides();
+        config
+    }
+
+    fn apply_env_overrides(&mut self) {
+        if let Ok(model) = std::env::var("KNOCK_MODEL") {
+            self.model = model;
+        }
+        if let Some(temperature) = parsed_env("KNOCK_TEMPERATURE") {
+            self.temperature = temperature;
+        }
+        if let Some(max_tokens) = parsed_env("KNOCK_MAX_TOKENS_STANDARD") {
+            self.max_tokens_standard = max_tokens;
+        }
+        if let Some(max_tokens) = parsed_env("KNOCK_MAX_TOKENS_VERBOSE") {
+            self.max_tokens_verbose = max_tokens;
+        }
+        if let Ok(clipboard_command) = std::env::var("KNOCK_CLIPBOARD_COMMAND") {
+            self.clipboard_command = Some(clipboard_command);
+        }
+        if let Ok(provider) = std::env::var("KNOCK_PROVIDER") {
+            self.provider = provider;
+        }
+        if let Ok(base_url) = std::env::var("KNOCK_BASE_URL") {
+            self.base_url = Some(base_url);
+        }
+        if let Ok(api_key_env) = std::env::var("KNOCK_API_KEY_ENV") {
+            self.api_key_env = Some(api_key_env);
+        }
+        if let Some(cache_max_age_secs) = parsed_env("KNOCK_CACHE_MAX_AGE_SECS") {
+            self.cache_max_age_secs = cache_max_age_secs;
+        }
+    }
+
+    pub fn get_field(&self, field: &str) -> Option<String> {
+        match field {
+            "model" => Some(self.model.clone()),
+            "temperature" => Some(self.temperature.to_string()),
+            "max_tokens_standard" => Some(self.max_tokens_standard.to_string()),
+            "max_tokens_verbose" => Some(self.max_tokens_verbose.to_string()),
+            "clipboard_command" => self.clipboard_command.clone(),
+            "provider" => Some(self.provider.clone()),
+            "base_url" => self.base_url.clone(),
+            "api_key_env" => self.api_key_env.clone(),
+            "cache_max_age_secs" => Some(self.cache_max_age_secs.to_string()),
+            _ => None,
+        }
+    }
+
+    pub fn set_field(&mut self, field: &str, value: &str) -> anyhow::Result<()> {
+        match field {
+            "model" => self.model = value.to_string(),
+            "temperature" => self.temperature = value.parse()?,
+            "max_tokens_standard" => self.max_tokens_standard = value.parse()?,
+            "max_tokens_verbose" => self.max_tokens_verbose = value.parse()?,
+            "clipboard_command" => self.clipboard_command = Some(value.to_string()),
+            "provider" => self.provider = value.to_string(),
+            "base_url" => self.base_url = Some(value.to_string()),
+            "api_key_env" => self.api_key_env = Some(value.to_string()),
+            "cache_max_age_secs" => self.cache_max_age_secs = value.parse()?,
+            other => anyhow::bail!("unknown config field: {other}"),
+        }
+        Ok(())
+    }
+
+    pub fn save(&self) -> anyhow::Result<()> {
+        let path = config_path();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, toml::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
 
-    std::env::var("API_KEY").expect("API_KEY missing")
+fn parsed_env<T: std::str::FromStr>(key: &str) -> Option<T> {
+    std::env::var(key).ok().and_then(|value| value.parse().ok())
 }
@@ -0,0 +1,59 @@
+use std::env;
+use std::path::Path;
+
+/// Host platform details, detected once per invocation and fed to the model
+/// so it stops having to guess the OS/shell from context clues in the query.
+pub struct Environment {
+    pub os: String,
+    pub shell: String,
+    pub managers: Vec<String>,
+}
+
+const CANDIDATE_MANAGERS: &[&str] = &["brew", "apt", "dnf", "pacman", "winget"];
+
+impl Environment {
+    pub fn detect() -> Self {
+        Self {
+            os: env::consts::OS.to_string(),
+            shell: Self::detect_shell(),
+            managers: Self::detect_managers(),
+        }
+    }
+
+    fn detect_shell() -> String {
+        if cfg!(target_os = "windows") {
+            return env::var("COMSPEC")
+                .ok()
+                .and_then(|path| Self::basename(&path))
+                .unwrap_or_else(|| "cmd".to_string());
+        }
+
+        env::var("SHELL")
+            .ok()
+            .and_then(|path| Self::basename(&path))
+            .unwrap_or_else(|| "sh".to_string())
+    }
+
+    fn basename(path: &str) -> Option<String> {
+        Path::new(path)
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+    }
+
+    /// Package managers from `CANDIDATE_MANAGERS` that are actually on `PATH`.
+    fn detect_managers() -> Vec<String> {
+        let Some(path_var) = env::var_os("PATH") else {
+            return Vec::new();
+        };
+        let dirs: Vec<_> = env::split_paths(&path_var).collect();
+
+        CANDIDATE_MANAGERS
+            .iter()
+            .filter(|manager| {
+                let file_name = format!("{manager}{}", env::consts::EXE_SUFFIX);
+                dirs.iter().any(|dir| dir.join(&file_name).is_file())
+            })
+            .map(|manager| manager.to_string())
+            .collect()
+    }
+}
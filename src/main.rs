@@ -1,42 +1,43 @@
 mod args;
+mod backend;
+mod cache;
+mod clipboard;
 mod client;
+mod commands;
 mod config;
+mod environment;
+mod shell;
 
-use std::io::Write;
-use std::process::Command;
-use std::process::Stdio;
+use clap::{CommandFactory, Parser};
 
 use crate::args::Args;
-use crate::client::OpenAIClient;
-use clap::Parser;
-use colored::*;
+use crate::cache::Cache;
+use crate::commands::{Commands, Context, Execute, RunCommand};
+use crate::config::Config;
 
 #[tokio::main]
-async fn main() {
-    let client = OpenAIClient::new();
+async fn main() -> anyhow::Result<()> {
     let args = Args::parse();
-    let prompt = OpenAIClient::gen_prompt(&args);
-    let res = client
-        .send_prompt(&prompt)
-        .await
-        .expect("Error getting response");
-    println!("{}", &res.bright_cyan());
-    if !(&args.verbose) {
-        copy_to_clipboard(&res).expect("Error copying to clipboard");
-        // println!("{}", "result copied to clipboard!".red());
-    }
-}
-
-fn copy_to_clipboard(text: &str) -> Result<(), Box<dyn std::error::Error>> {
-    #[cfg(target_os = "macos")]
-    let cmd = "pbcopy";
-
-    #[cfg(target_os = "linux")]
-    let cmd = "wl-copy";
+    let ctx = Context {
+        cache: Cache::load(),
+        config: Config::load(),
+        verbose: args.verbose,
+        no_cache: args.no_cache,
+        execute: args.execute,
+        force: args.force,
+    };
 
-    let mut child = Command::new(cmd).stdin(Stdio::piped()).spawn()?;
+    let command = match args.command {
+        Some(command) => command,
+        None => match args.input {
+            Some(input) => Commands::Run(RunCommand { input }),
+            None => {
+                Args::command().print_help()?;
+                println!();
+                return Ok(());
+            }
+        },
+    };
 
-    child.stdin.as_mut().unwrap().write_all(text.as_bytes())?;
-    child.wait()?;
-    Ok(())
+    command.execute(&ctx).await
 }
@@ -0,0 +1,84 @@
+use std::io::{self, Write};
+use std::process::{Command, ExitStatus};
+
+use crate::environment::Environment;
+
+const DANGEROUS_WORDS: &[&str] = &["rm", "mkfs", "dd", "drop"];
+const DANGEROUS_SUBSTRINGS: &[&str] = &[":>"];
+
+fn looks_destructive(command: &str) -> bool {
+    let lower = command.to_lowercase();
+    let has_dangerous_word = lower
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| DANGEROUS_WORDS.contains(&word));
+    let has_dangerous_substring = DANGEROUS_SUBSTRINGS.iter().any(|s| lower.contains(s));
+    has_dangerous_word || has_dangerous_substring
+}
+
+fn prompt(message: &str) -> anyhow::Result<String> {
+    print!("{message}");
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer)?;
+    Ok(answer.trim().to_string())
+}
+
+fn is_yes(answer: &str) -> bool {
+    matches!(answer, "y" | "yes")
+}
+
+/// Prompts the user to run `command` (optionally letting them edit it first),
+/// then executes it in the detected shell (PowerShell on Windows) with
+/// inherited stdio.
+///
+/// Commands that look destructive are refused outright unless `force` is
+/// set, and even then require a second, explicit confirmation.
+pub fn confirm_and_run(command: &str, force: bool) -> anyhow::Result<()> {
+    let mut command = command.to_string();
+
+    loop {
+        match prompt("Run this? [y/N/edit] ")?.to_lowercase().as_str() {
+            answer if is_yes(answer) => break,
+            "edit" | "e" => {
+                let edited = prompt("Edit command: ")?;
+                if !edited.is_empty() {
+                    command = edited;
+                }
+            }
+            _ => {
+                println!("Aborted.");
+                return Ok(());
+            }
+        }
+    }
+
+    if looks_destructive(&command) {
+        if !force {
+            println!(
+                "Refusing to run a potentially destructive command without --force: {command}"
+            );
+            return Ok(());
+        }
+        if !is_yes(&prompt("This command looks destructive. Are you sure? [y/N] ")?.to_lowercase()) {
+            println!("Aborted.");
+            return Ok(());
+        }
+    }
+
+    let env = Environment::detect();
+    let status = run_in_shell(&env, &command)?;
+    println!("Exit status: {status}");
+    Ok(())
+}
+
+#[cfg(target_os = "windows")]
+fn run_in_shell(_env: &Environment, command: &str) -> anyhow::Result<ExitStatus> {
+    Ok(Command::new("powershell")
+        .args(["-Command", command])
+        .status()?)
+}
+
+#[cfg(not(target_os = "windows"))]
+fn run_in_shell(env: &Environment, command: &str) -> anyhow::Result<ExitStatus> {
+    Ok(Command::new(&env.shell).arg("-c").arg(command).status()?)
+}